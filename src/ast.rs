@@ -1,3 +1,4 @@
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub enum Precedence {
     Lowest,
     Equals,      // ==
@@ -14,12 +15,17 @@ pub struct Ident(pub String);
 #[derive(Debug, Clone, PartialEq)]
 pub enum Literal {
     Int(i64),
+    Float(f64),
+    Str(String),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expr {
     Literal(Literal),
     Ident(Ident),
+    Boolean(bool),
+    Prefix(String, Box<Expr>),
+    Infix(Box<Expr>, String, Box<Expr>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1,31 +1,77 @@
+use unicode_xid::UnicodeXID;
+
 use crate::token::Token;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub token: T,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexError {
+    pub message: String,
+    pub span: Span,
+}
+
+fn is_ident_start(ch: char) -> bool {
+    ch == '_' || ch.is_xid_start()
+}
+
+fn is_ident_continue(ch: char) -> bool {
+    ch == '_' || ch.is_xid_continue()
+}
+
 pub struct Lexer<'a> {
     input: &'a str,
+    chars: Vec<(usize, char)>,
     position: usize,
     read_position: usize,
-    ch: u8,
+    ch: char,
+    line: usize,
+    column: usize,
+    errors: Vec<LexError>,
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(input: &'a str) -> Self {
         let mut lexer = Lexer {
             input,
+            chars: input.char_indices().collect(),
             position: 0,
             read_position: 0,
-            ch: 0,
+            ch: '\0',
+            line: 1,
+            column: 0,
+            errors: vec![],
         };
 
         lexer.read_char();
         lexer
     }
 
-    pub fn next(&mut self) -> Token {
-        self.skip_whitespace();
+    pub fn errors(&self) -> &[LexError] {
+        &self.errors
+    }
 
-        match self.ch {
-            b'=' => {
-                if self.peek_char() == b'=' {
+    pub fn next_spanned(&mut self) -> Spanned<Token> {
+        self.skip_trivia();
+
+        let start = self.byte_offset(self.position);
+        let line = self.line;
+        let column = self.column;
+
+        let token = match self.ch {
+            '=' => {
+                if self.peek_char() == '=' {
                     self.read_char();
                     self.read_char();
                     Token::Eq
@@ -34,48 +80,60 @@ impl<'a> Lexer<'a> {
                     Token::Assign
                 }
             }
-            b';' => {
+            ';' => {
                 self.read_char();
                 Token::Semicolon
             }
-            b'(' => {
+            '(' => {
                 self.read_char();
                 Token::Lparen
             }
-            b')' => {
+            ')' => {
                 self.read_char();
                 Token::Rparen
             }
-            b',' => {
+            ',' => {
                 self.read_char();
                 Token::Comma
             }
-            b'+' => {
+            '+' => {
                 self.read_char();
                 Token::Plus
             }
-            b'-' => {
+            '-' => {
                 self.read_char();
                 Token::Minus
             }
-            b'/' => {
+            '/' => {
                 self.read_char();
                 Token::Slash
             }
-            b'*' => {
+            '*' => {
                 self.read_char();
                 Token::Asterisk
             }
-            b'<' => {
-                self.read_char();
-                Token::Lt
+            '<' => {
+                if self.peek_char() == '=' {
+                    self.read_char();
+                    self.read_char();
+                    Token::Le
+                } else {
+                    self.read_char();
+                    Token::Lt
+                }
             }
-            b'>' => {
-                self.read_char();
-                Token::Gt
+            '>' => {
+                if self.peek_char() == '=' {
+                    self.read_char();
+                    self.read_char();
+                    Token::Ge
+                } else {
+                    self.read_char();
+                    Token::Gt
+                }
             }
-            b'!' => {
-                if self.peek_char() == b'=' {
+            '!' => {
+                if self.peek_char() == '=' {
                     self.read_char();
                     self.read_char();
                     Token::NotEq
@@ -84,42 +142,68 @@ impl<'a> Lexer<'a> {
                     Token::Bang
                 }
             }
-            b'{' => {
+            '{' => {
                 self.read_char();
                 Token::Lbrace
             }
-            b'}' => {
+            '}' => {
                 self.read_char();
                 Token::Rbrace
             }
-            b'a'..=b'z' | b'A'..=b'Z' | b'_' => self.read_identifier(),
-            b'0'..=b'9' => self.read_number(),
-            0 => {
+            '"' => self.read_string(),
+            '\0' => {
                 self.read_char();
                 Token::Eof
             }
-            _ => {
+            ch if is_ident_start(ch) => self.read_identifier(),
+            ch if ch.is_ascii_digit() => self.read_number(),
+            ch => {
                 self.read_char();
+                let span = Span {
+                    start,
+                    end: self.byte_offset(self.position),
+                    line,
+                    column,
+                };
+                self.errors.push(LexError {
+                    message: format!("unexpected character `{}`", ch),
+                    span,
+                });
                 Token::Illegal
             }
-        }
+        };
+
+        let span = Span {
+            start,
+            end: self.byte_offset(self.position),
+            line,
+            column,
+        };
+
+        Spanned { token, span }
     }
 
-    fn peek_char(&self) -> u8 {
-        if self.read_position >= self.input.len() {
-            0
-        } else {
-            self.input.as_bytes()[self.read_position]
-        }
+    fn byte_offset(&self, idx: usize) -> usize {
+        self.chars
+            .get(idx)
+            .map(|&(b, _)| b)
+            .unwrap_or(self.input.len())
+    }
+
+    fn peek_char(&self) -> char {
+        self.chars
+            .get(self.read_position)
+            .map(|&(_, ch)| ch)
+            .unwrap_or('\0')
     }
 
     fn read_identifier(&mut self) -> Token {
         let start = self.position;
-        while let b'a'..=b'z' | b'A'..=b'Z' | b'_' = self.ch {
+        while is_ident_continue(self.ch) {
             self.read_char();
         }
 
-        let literal = &self.input[start..self.position];
+        let literal = &self.input[self.byte_offset(start)..self.byte_offset(self.position)];
         match literal {
             "fn" => Token::Func,
             "let" => Token::Let,
@@ -134,41 +218,399 @@ impl<'a> Lexer<'a> {
 
     fn read_number(&mut self) -> Token {
         let start = self.position;
-        while let b'0'..=b'9' = self.ch {
+        let start_byte = self.byte_offset(start);
+        let start_line = self.line;
+        let start_column = self.column;
+
+        while self.ch.is_ascii_digit() {
             self.read_char();
         }
 
-        let literal = &self.input[start..self.position];
-        Token::Int(literal.parse().unwrap())
+        let mut is_float = false;
+        if self.ch == '.' && self.peek_char().is_ascii_digit() {
+            is_float = true;
+            self.read_char();
+            while self.ch.is_ascii_digit() {
+                self.read_char();
+            }
+        }
+
+        let literal = &self.input[start_byte..self.byte_offset(self.position)];
+        if is_float {
+            Token::Float(literal.to_string())
+        } else {
+            match literal.parse() {
+                Ok(value) => Token::Int(value),
+                Err(_) => {
+                    self.errors.push(LexError {
+                        message: format!("integer literal `{}` overflows i64", literal),
+                        span: Span {
+                            start: start_byte,
+                            end: self.byte_offset(self.position),
+                            line: start_line,
+                            column: start_column,
+                        },
+                    });
+                    Token::Illegal
+                }
+            }
+        }
+    }
+
+    fn read_string(&mut self) -> Token {
+        let start_line = self.line;
+        let start_column = self.column;
+        let byte_start = self.byte_offset(self.position);
+        let mut value = String::new();
+
+        self.read_char();
+
+        loop {
+            match self.ch {
+                '"' => {
+                    self.read_char();
+                    break;
+                }
+                '\0' => {
+                    self.errors.push(LexError {
+                        message: "unterminated string literal".to_string(),
+                        span: Span {
+                            start: byte_start,
+                            end: self.byte_offset(self.position),
+                            line: start_line,
+                            column: start_column,
+                        },
+                    });
+                    break;
+                }
+                '\\' => {
+                    self.read_char();
+                    match self.ch {
+                        'n' => value.push('\n'),
+                        't' => value.push('\t'),
+                        '"' => value.push('"'),
+                        '\\' => value.push('\\'),
+                        '\0' => {}
+                        ch => value.push(ch),
+                    }
+                    self.read_char();
+                }
+                ch => {
+                    value.push(ch);
+                    self.read_char();
+                }
+            }
+        }
+
+        Token::String(value)
     }
 
     fn read_char(&mut self) {
-        if self.read_position >= self.input.len() {
-            self.ch = 0;
+        if self.ch == '\n' {
+            self.line += 1;
+            self.column = 1;
         } else {
-            self.ch = self.input.as_bytes()[self.read_position];
+            self.column += 1;
         }
+
+        self.ch = self
+            .chars
+            .get(self.read_position)
+            .map(|&(_, ch)| ch)
+            .unwrap_or('\0');
         self.position = self.read_position;
         self.read_position += 1;
     }
 
     fn skip_whitespace(&mut self) {
-        while let b' ' | b'\n' | b'\t' | b'\r' = self.ch {
+        while let ' ' | '\n' | '\t' | '\r' = self.ch {
             self.read_char();
         }
     }
+
+    fn skip_trivia(&mut self) {
+        loop {
+            self.skip_whitespace();
+
+            if self.ch == '/' && self.peek_char() == '/' {
+                while self.ch != '\n' && self.ch != '\0' {
+                    self.read_char();
+                }
+            } else if self.ch == '/' && self.peek_char() == '*' {
+                self.skip_block_comment();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn skip_block_comment(&mut self) {
+        let line = self.line;
+        let column = self.column;
+        let byte_start = self.byte_offset(self.position);
+
+        self.read_char();
+        self.read_char();
+
+        loop {
+            if self.ch == '*' && self.peek_char() == '/' {
+                self.read_char();
+                self.read_char();
+                break;
+            }
+
+            if self.ch == '\0' {
+                self.errors.push(LexError {
+                    message: "unterminated block comment".to_string(),
+                    span: Span {
+                        start: byte_start,
+                        end: self.byte_offset(self.position),
+                        line,
+                        column,
+                    },
+                });
+                break;
+            }
+
+            self.read_char();
+        }
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        let spanned = Lexer::next_spanned(self);
+        if spanned.token == Token::Eof {
+            None
+        } else {
+            Some(spanned.token)
+        }
+    }
+}
+
+pub fn tokenize(input: &str) -> (Vec<Spanned<Token>>, Vec<LexError>) {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = vec![];
+
+    loop {
+        let spanned = lexer.next_spanned();
+        let is_eof = spanned.token == Token::Eof;
+        tokens.push(spanned);
+        if is_eof {
+            break;
+        }
+    }
+
+    (tokens, lexer.errors().to_vec())
+}
+
+pub fn render_errors(source: &str, errors: &[LexError]) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut output = String::new();
+
+    for error in errors {
+        let line = lines.get(error.span.line - 1).copied().unwrap_or("");
+        let underline = " ".repeat(error.span.column.saturating_sub(1));
+        output.push_str(&format!("{}\n{}^ {}\n", line, underline, error.message));
+    }
+
+    output
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn tokens_of(input: &str) -> Vec<Token> {
+        let (tokens, _) = tokenize(input);
+        tokens.into_iter().map(|s| s.token).collect()
+    }
+
+    #[test]
+    fn illegal_character_is_reported_with_span() {
+        let input = "let x = 5 @ 3;";
+        let (tokens, errors) = tokenize(input);
+
+        assert_eq!(1, errors.len());
+        assert_eq!(errors[0].span.column, 11);
+        assert!(tokens.iter().any(|s| s.token == Token::Illegal));
+    }
+
+    #[test]
+    fn render_errors_points_at_offending_column() {
+        let input = "let x = @;";
+        let (_, errors) = tokenize(input);
+
+        let rendered = render_errors(input, &errors);
+        assert!(rendered.contains(input));
+        assert!(rendered.contains("\n        ^ unexpected character `@`"));
+    }
+
+    #[test]
+    fn iterator_yields_tokens_without_eof() {
+        let input = "let x = 5;";
+        let tokens: Vec<Token> = Lexer::new(input).collect();
+
+        assert_eq!(
+            vec![
+                Token::Let,
+                Token::Ident("x".to_string()),
+                Token::Assign,
+                Token::Int(5),
+                Token::Semicolon,
+            ],
+            tokens
+        );
+    }
+
+    #[test]
+    fn unicode_identifiers() {
+        let input = "let σ = 5; let café = σ;";
+        let expected_tokens = vec![
+            Token::Let,
+            Token::Ident("σ".to_string()),
+            Token::Assign,
+            Token::Int(5),
+            Token::Semicolon,
+            Token::Let,
+            Token::Ident("café".to_string()),
+            Token::Assign,
+            Token::Ident("σ".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        assert_eq!(expected_tokens, tokens_of(input));
+    }
+
+    #[test]
+    fn unicode_identifier_spans_use_byte_offsets() {
+        let input = "café";
+        let mut lexer = Lexer::new(input);
+        let spanned = lexer.next_spanned();
+
+        assert_eq!(spanned.token, Token::Ident("café".to_string()));
+        assert_eq!(spanned.span.start, 0);
+        assert_eq!(spanned.span.end, input.len());
+    }
+
+    #[test]
+    fn string_literals_with_escapes() {
+        let input = r#""hello\nworld" "quote: \" backslash: \\""#;
+        let expected_tokens = vec![
+            Token::String("hello\nworld".to_string()),
+            Token::String("quote: \" backslash: \\".to_string()),
+            Token::Eof,
+        ];
+
+        assert_eq!(expected_tokens, tokens_of(input));
+    }
+
+    #[test]
+    fn unterminated_string_reports_error() {
+        let input = "\"unterminated";
+        let (tokens, errors) = tokenize(input);
+
+        assert_eq!(1, errors.len());
+        assert_eq!(tokens[0].token, Token::String("unterminated".to_string()));
+    }
+
+    #[test]
+    fn unterminated_string_with_trailing_backslash_does_not_corrupt_value() {
+        let input = "\"abc\\";
+        let (tokens, errors) = tokenize(input);
+
+        assert_eq!(1, errors.len());
+        assert_eq!(tokens[0].token, Token::String("abc".to_string()));
+    }
+
+    #[test]
+    fn integer_overflow_reports_error() {
+        let input = "99999999999999999999999;";
+        let (tokens, errors) = tokenize(input);
+
+        assert_eq!(1, errors.len());
+        assert_eq!(tokens[0].token, Token::Illegal);
+    }
+
+    #[test]
+    fn float_literals() {
+        let input = "3.14 0.5 10;";
+        let expected_tokens = vec![
+            Token::Float("3.14".to_string()),
+            Token::Float("0.5".to_string()),
+            Token::Int(10),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        assert_eq!(expected_tokens, tokens_of(input));
+    }
+
+    #[test]
+    fn line_comments_are_skipped() {
+        let input = "
+        let x = 5; // this is a comment
+        let y = 10;
+        ";
+        let expected_tokens = vec![
+            Token::Let,
+            Token::Ident("x".to_string()),
+            Token::Assign,
+            Token::Int(5),
+            Token::Semicolon,
+            Token::Let,
+            Token::Ident("y".to_string()),
+            Token::Assign,
+            Token::Int(10),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        assert_eq!(expected_tokens, tokens_of(input));
+    }
+
+    #[test]
+    fn block_comments_are_skipped() {
+        let input = "
+        let x /* inline comment */ = 5;
+        /* a comment
+           spanning multiple lines */
+        let y = 10;
+        ";
+        let expected_tokens = vec![
+            Token::Let,
+            Token::Ident("x".to_string()),
+            Token::Assign,
+            Token::Int(5),
+            Token::Semicolon,
+            Token::Let,
+            Token::Ident("y".to_string()),
+            Token::Assign,
+            Token::Int(10),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        assert_eq!(expected_tokens, tokens_of(input));
+    }
+
+    #[test]
+    fn unterminated_block_comment_reports_error() {
+        let input = "let x = 5; /* never closed";
+        let (_, errors) = tokenize(input);
+
+        assert_eq!(1, errors.len());
+        assert_eq!(errors[0].message, "unterminated block comment");
+    }
+
     #[test]
     fn basic_symbols() {
         let input = "
         =+(){},;
         ";
-        let mut lexer = Lexer::new(input);
         let expected_tokens = vec![
             Token::Assign,
             Token::Plus,
@@ -181,10 +623,7 @@ mod tests {
             Token::Eof,
         ];
 
-        for expected in expected_tokens {
-            let actual = lexer.next();
-            assert_eq!(expected, actual);
-        }
+        assert_eq!(expected_tokens, tokens_of(input));
     }
 
     #[test]
@@ -193,7 +632,6 @@ mod tests {
         let five = 5;
         let ten = 10;
         ";
-        let mut lexer = Lexer::new(input);
         let expected_tokens = vec![
             Token::Let,
             Token::Ident("five".to_string()),
@@ -208,10 +646,7 @@ mod tests {
             Token::Eof,
         ];
 
-        for expected in expected_tokens {
-            let actual = lexer.next();
-            assert_eq!(expected, actual);
-        }
+        assert_eq!(expected_tokens, tokens_of(input));
     }
 
     #[test]
@@ -224,7 +659,6 @@ mod tests {
         };
         let result = add(five, ten);
         ";
-        let mut lexer = Lexer::new(input);
         let expected_tokens = vec![
             Token::Let,
             Token::Ident("five".to_string()),
@@ -265,19 +699,15 @@ mod tests {
             Token::Eof,
         ];
 
-        for expected in expected_tokens {
-            let actual = lexer.next();
-            assert_eq!(expected, actual);
-        }
+        assert_eq!(expected_tokens, tokens_of(input));
     }
 
     #[test]
     fn more_symbols() {
         let input = "
-        !-/*5;
+        !-/ *5;
         5 < 10 > 5;
         ";
-        let mut lexer = Lexer::new(input);
         let expected_tokens = vec![
             Token::Bang,
             Token::Minus,
@@ -294,10 +724,7 @@ mod tests {
             Token::Eof,
         ];
 
-        for expected in expected_tokens {
-            let actual = lexer.next();
-            assert_eq!(expected, actual);
-        }
+        assert_eq!(expected_tokens, tokens_of(input));
     }
 
     #[test]
@@ -309,7 +736,6 @@ mod tests {
             return false;
         }
         ";
-        let mut lexer = Lexer::new(input);
         let expected_tokens = vec![
             Token::If,
             Token::Lparen,
@@ -331,10 +757,7 @@ mod tests {
             Token::Eof,
         ];
 
-        for expected in expected_tokens {
-            let actual = lexer.next();
-            assert_eq!(expected, actual);
-        }
+        assert_eq!(expected_tokens, tokens_of(input));
     }
 
     #[test]
@@ -343,7 +766,6 @@ mod tests {
         10 == 10;
         10 != 9;
         ";
-        let mut lexer = Lexer::new(input);
         let expected_tokens = vec![
             Token::Int(10),
             Token::Eq,
@@ -356,9 +778,54 @@ mod tests {
             Token::Eof,
         ];
 
-        for expected in expected_tokens {
-            let actual = lexer.next();
-            assert_eq!(expected, actual);
+        assert_eq!(expected_tokens, tokens_of(input));
+    }
+
+    #[test]
+    fn less_greater_or_equal() {
+        let input = "
+        5 <= 10;
+        10 >= 5;
+        ";
+        let expected_tokens = vec![
+            Token::Int(5),
+            Token::Le,
+            Token::Int(10),
+            Token::Semicolon,
+            Token::Int(10),
+            Token::Ge,
+            Token::Int(5),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        assert_eq!(expected_tokens, tokens_of(input));
+    }
+
+    #[test]
+    fn spans_track_line_and_column() {
+        let input = "let x = 5;\nlet y = 10;";
+        let mut lexer = Lexer::new(input);
+
+        let let_tok = lexer.next_spanned();
+        assert_eq!(
+            let_tok.span,
+            Span {
+                start: 0,
+                end: 3,
+                line: 1,
+                column: 1
+            }
+        );
+
+        let mut spanned = lexer.next_spanned();
+        while spanned.token != Token::Semicolon {
+            spanned = lexer.next_spanned();
         }
+
+        let next_let = lexer.next_spanned();
+        assert_eq!(next_let.token, Token::Let);
+        assert_eq!(next_let.span.line, 2);
+        assert_eq!(next_let.span.column, 1);
     }
 }
@@ -1,5 +1,4 @@
-use monkey_rs::lexer::Lexer;
-use monkey_rs::token::Token;
+use monkey_rs::lexer::{render_errors, tokenize};
 use rustyline::error::ReadlineError;
 use rustyline::{DefaultEditor, Result};
 
@@ -18,14 +17,13 @@ fn main() -> Result<()> {
         let readline = rl.readline(">> ");
         match readline {
             Ok(line) => {
-                let mut lexer = Lexer::new(&line);
-                loop {
-                    let next = lexer.next();
-                    if next == Token::Eof {
-                        break;
-                    } else {
-                        println!("{:?}", next);
+                let (tokens, errors) = tokenize(&line);
+                if errors.is_empty() {
+                    for spanned in tokens {
+                        println!("{:?}", spanned.token);
                     }
+                } else {
+                    print!("{}", render_errors(&line, &errors));
                 }
             }
             Err(ReadlineError::Interrupted) => {
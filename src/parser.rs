@@ -1,7 +1,7 @@
 use core::fmt;
 
 use crate::{
-    ast::{self},
+    ast::{self, Precedence},
     lexer::Lexer,
     token::Token,
 };
@@ -39,6 +39,17 @@ impl fmt::Display for ParseError {
 
 pub type ParseErrors = Vec<ParseError>;
 
+fn token_precedence(tok: &Token) -> Precedence {
+    match tok {
+        Token::Eq | Token::NotEq => Precedence::Equals,
+        Token::Lt | Token::Gt | Token::Le | Token::Ge => Precedence::LessGreater,
+        Token::Plus | Token::Minus => Precedence::Sum,
+        Token::Slash | Token::Asterisk => Precedence::Product,
+        Token::Lparen => Precedence::Call,
+        _ => Precedence::Lowest,
+    }
+}
+
 pub struct Parser<'a> {
     pub lexer: Lexer<'a>,
     pub curr_token: Token,
@@ -67,7 +78,7 @@ impl<'a> Parser<'a> {
 
     pub fn next_token(&mut self) {
         self.curr_token = self.peek_token.clone();
-        self.peek_token = self.lexer.next();
+        self.peek_token = self.lexer.next_spanned().token;
     }
 
     pub fn curr_token_is(&mut self, tok: Token) -> bool {
@@ -98,24 +109,49 @@ impl<'a> Parser<'a> {
         ));
     }
 
+    fn curr_precedence(&self) -> Precedence {
+        token_precedence(&self.curr_token)
+    }
+
+    fn peek_precedence(&self) -> Precedence {
+        token_precedence(&self.peek_token)
+    }
+
     pub fn parse_program(&mut self) -> ast::Program {
         let mut program: ast::Program = vec![];
 
         while !self.curr_token_is(Token::Eof) {
             match self.parse_statement() {
-                Some(statement) => program.push(statement),
-                None => {}
+                Some(statement) => {
+                    program.push(statement);
+                    self.next_token();
+                }
+                None => self.recover_to_next_statement(),
             }
-            self.next_token();
         }
 
         program
     }
 
+    // On a parse error, leftover tokens from the broken statement would
+    // otherwise be fed one at a time into parse_expression_statement,
+    // producing a cascade of unrelated "no prefix parse function" errors.
+    // Skip to the next statement boundary instead.
+    fn recover_to_next_statement(&mut self) {
+        while !self.curr_token_is(Token::Semicolon) && !self.curr_token_is(Token::Eof) {
+            self.next_token();
+        }
+
+        if self.curr_token_is(Token::Semicolon) {
+            self.next_token();
+        }
+    }
+
     pub fn parse_statement(&mut self) -> Option<ast::Statement> {
         match self.curr_token {
             Token::Let => self.parse_let_statement(),
-            _ => None,
+            Token::Return => self.parse_return_statement(),
+            _ => self.parse_expression_statement(),
         }
     }
 
@@ -139,10 +175,7 @@ impl<'a> Parser<'a> {
 
         self.next_token();
 
-        let expr = match self.parse_expression() {
-            Some(expr) => expr,
-            None => return None,
-        };
+        let expr = self.parse_expression(Precedence::Lowest)?;
 
         if self.peek_token_is(&Token::Semicolon) {
             self.next_token();
@@ -151,11 +184,69 @@ impl<'a> Parser<'a> {
         Some(ast::Statement::Let(name, expr))
     }
 
-    pub fn parse_expression(&mut self) -> Option<ast::Expr> {
+    pub fn parse_return_statement(&mut self) -> Option<ast::Statement> {
+        self.next_token();
+
+        let expr = self.parse_expression(Precedence::Lowest)?;
+
+        if self.peek_token_is(&Token::Semicolon) {
+            self.next_token();
+        }
+
+        Some(ast::Statement::Return(expr))
+    }
+
+    pub fn parse_expression_statement(&mut self) -> Option<ast::Statement> {
+        let expr = self.parse_expression(Precedence::Lowest)?;
+
+        if self.peek_token_is(&Token::Semicolon) {
+            self.next_token();
+        }
+
+        Some(ast::Statement::Expr(expr))
+    }
+
+    pub fn parse_expression(&mut self, precedence: Precedence) -> Option<ast::Expr> {
+        let mut left = self.parse_prefix_expression()?;
+
+        while !self.peek_token_is(&Token::Semicolon) && precedence < self.peek_precedence() {
+            match self.peek_token {
+                Token::Plus
+                | Token::Minus
+                | Token::Slash
+                | Token::Asterisk
+                | Token::Eq
+                | Token::NotEq
+                | Token::Lt
+                | Token::Gt
+                | Token::Le
+                | Token::Ge => {
+                    self.next_token();
+                    left = self.parse_infix_expression(left)?;
+                }
+                _ => return Some(left),
+            }
+        }
+
+        Some(left)
+    }
+
+    fn parse_prefix_expression(&mut self) -> Option<ast::Expr> {
         match self.curr_token {
             Token::Ident(_) => self.parse_ident_expression(),
             Token::Int(_) => self.parse_int_expression(),
-            _ => None,
+            Token::Float(_) => self.parse_float_expression(),
+            Token::String(_) => self.parse_string_expression(),
+            Token::True | Token::False => self.parse_boolean_expression(),
+            Token::Bang | Token::Minus => self.parse_prefix_operator_expression(),
+            Token::Lparen => self.parse_grouped_expression(),
+            _ => {
+                self.errors.push(ParseError::new(
+                    ParseErrorKind::UnexpectedToken,
+                    format!("no prefix parse function for {:?} found", self.curr_token),
+                ));
+                None
+            }
         }
     }
 
@@ -183,6 +274,83 @@ impl<'a> Parser<'a> {
             _ => None,
         }
     }
+
+    fn parse_float_expression(&mut self) -> Option<ast::Expr> {
+        match self.curr_token {
+            Token::Float(ref s) => match s.parse::<f64>() {
+                Ok(f) => Some(ast::Expr::Literal(ast::Literal::Float(f))),
+                Err(_) => {
+                    self.errors.push(ParseError::new(
+                        ParseErrorKind::UnexpectedToken,
+                        format!("could not parse {:?} as float", s),
+                    ));
+                    None
+                }
+            },
+            _ => None,
+        }
+    }
+
+    fn parse_string_expression(&mut self) -> Option<ast::Expr> {
+        match self.curr_token {
+            Token::String(ref s) => Some(ast::Expr::Literal(ast::Literal::Str(s.clone()))),
+            _ => None,
+        }
+    }
+
+    fn parse_boolean_expression(&mut self) -> Option<ast::Expr> {
+        Some(ast::Expr::Boolean(self.curr_token_is(Token::True)))
+    }
+
+    fn parse_prefix_operator_expression(&mut self) -> Option<ast::Expr> {
+        let operator = match self.curr_token {
+            Token::Bang => "!",
+            Token::Minus => "-",
+            _ => unreachable!(),
+        }
+        .to_string();
+
+        self.next_token();
+
+        let right = self.parse_expression(Precedence::Prefix)?;
+
+        Some(ast::Expr::Prefix(operator, Box::new(right)))
+    }
+
+    fn parse_infix_expression(&mut self, left: ast::Expr) -> Option<ast::Expr> {
+        let operator = match self.curr_token {
+            Token::Plus => "+",
+            Token::Minus => "-",
+            Token::Slash => "/",
+            Token::Asterisk => "*",
+            Token::Eq => "==",
+            Token::NotEq => "!=",
+            Token::Lt => "<",
+            Token::Gt => ">",
+            Token::Le => "<=",
+            Token::Ge => ">=",
+            _ => unreachable!(),
+        }
+        .to_string();
+
+        let precedence = self.curr_precedence();
+        self.next_token();
+        let right = self.parse_expression(precedence)?;
+
+        Some(ast::Expr::Infix(Box::new(left), operator, Box::new(right)))
+    }
+
+    fn parse_grouped_expression(&mut self) -> Option<ast::Expr> {
+        self.next_token();
+
+        let expr = self.parse_expression(Precedence::Lowest)?;
+
+        if !self.expect_peek_token(Token::Rparen) {
+            return None;
+        }
+
+        Some(expr)
+    }
 }
 
 #[cfg(test)]
@@ -250,6 +418,25 @@ let foobar = 838383;"#;
         );
     }
 
+    #[test]
+    fn float_and_string_literals() {
+        let input = r#"
+3.14;
+"hi";"#;
+
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program();
+
+        assert_eq!(0, parser.get_errors().len());
+        assert_eq!(
+            vec![
+                ast::Statement::Expr(ast::Expr::Literal(ast::Literal::Float(3.14))),
+                ast::Statement::Expr(ast::Expr::Literal(ast::Literal::Str(String::from("hi")))),
+            ],
+            program
+        );
+    }
+
     #[test]
     fn let_statement_with_errors() {
         let input = r#"
@@ -261,4 +448,152 @@ let 838383;"#;
         let program = parser.parse_program();
         assert_eq!(3, parser.get_errors().len());
     }
+
+    #[test]
+    fn return_statements() {
+        let input = r#"
+return 5;
+return 10;
+return 993322;"#;
+
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program();
+
+        assert_eq!(0, parser.get_errors().len());
+        assert_eq!(
+            vec![
+                ast::Statement::Return(ast::Expr::Literal(ast::Literal::Int(5))),
+                ast::Statement::Return(ast::Expr::Literal(ast::Literal::Int(10))),
+                ast::Statement::Return(ast::Expr::Literal(ast::Literal::Int(993322))),
+            ],
+            program
+        );
+    }
+
+    #[test]
+    fn prefix_expressions() {
+        let tests = vec![
+            ("!5;", "!", ast::Expr::Literal(ast::Literal::Int(5))),
+            ("-15;", "-", ast::Expr::Literal(ast::Literal::Int(15))),
+            ("!true;", "!", ast::Expr::Boolean(true)),
+        ];
+
+        for (input, operator, right) in tests {
+            let mut parser = Parser::new(Lexer::new(input));
+            let program = parser.parse_program();
+
+            assert_eq!(0, parser.get_errors().len());
+            assert_eq!(
+                vec![ast::Statement::Expr(ast::Expr::Prefix(
+                    operator.to_string(),
+                    Box::new(right)
+                ))],
+                program
+            );
+        }
+    }
+
+    #[test]
+    fn infix_expressions() {
+        let tests = vec![
+            (
+                "5 + 5;",
+                ast::Expr::Literal(ast::Literal::Int(5)),
+                "+",
+                ast::Expr::Literal(ast::Literal::Int(5)),
+            ),
+            (
+                "5 == 5;",
+                ast::Expr::Literal(ast::Literal::Int(5)),
+                "==",
+                ast::Expr::Literal(ast::Literal::Int(5)),
+            ),
+            (
+                "5 != 5;",
+                ast::Expr::Literal(ast::Literal::Int(5)),
+                "!=",
+                ast::Expr::Literal(ast::Literal::Int(5)),
+            ),
+            (
+                "true == true;",
+                ast::Expr::Boolean(true),
+                "==",
+                ast::Expr::Boolean(true),
+            ),
+        ];
+
+        for (input, left, operator, right) in tests {
+            let mut parser = Parser::new(Lexer::new(input));
+            let program = parser.parse_program();
+
+            assert_eq!(0, parser.get_errors().len());
+            assert_eq!(
+                vec![ast::Statement::Expr(ast::Expr::Infix(
+                    Box::new(left),
+                    operator.to_string(),
+                    Box::new(right)
+                ))],
+                program
+            );
+        }
+    }
+
+    #[test]
+    fn operator_precedence() {
+        let tests = vec![
+            ("-a * b", "((-a) * b)"),
+            ("!-a", "(!(-a))"),
+            ("a + b + c", "((a + b) + c)"),
+            ("a + b - c", "((a + b) - c)"),
+            ("a * b * c", "((a * b) * c)"),
+            ("a + b * c", "(a + (b * c))"),
+            ("a + (b + c) + d", "((a + (b + c)) + d)"),
+            ("(5 + 5) * 2", "((5 + 5) * 2)"),
+            ("3 > 5 == false", "((3 > 5) == false)"),
+            ("3 < 5 == true", "((3 < 5) == true)"),
+        ];
+
+        for (input, expected) in tests {
+            let mut parser = Parser::new(Lexer::new(input));
+            let program = parser.parse_program();
+
+            assert_eq!(0, parser.get_errors().len());
+            assert_eq!(expected, format_program(&program));
+        }
+    }
+
+    fn format_program(program: &ast::Program) -> String {
+        program
+            .iter()
+            .map(format_statement)
+            .collect::<Vec<_>>()
+            .join("")
+    }
+
+    fn format_statement(statement: &ast::Statement) -> String {
+        match statement {
+            ast::Statement::Expr(expr) => format_expr(expr),
+            ast::Statement::Let(ident, expr) => format!("let {} = {};", ident.0, format_expr(expr)),
+            ast::Statement::Return(expr) => format!("return {};", format_expr(expr)),
+        }
+    }
+
+    fn format_expr(expr: &ast::Expr) -> String {
+        match expr {
+            ast::Expr::Ident(ast::Ident(name)) => name.clone(),
+            ast::Expr::Literal(ast::Literal::Int(value)) => value.to_string(),
+            ast::Expr::Literal(ast::Literal::Float(value)) => value.to_string(),
+            ast::Expr::Literal(ast::Literal::Str(value)) => value.clone(),
+            ast::Expr::Boolean(value) => value.to_string(),
+            ast::Expr::Prefix(operator, right) => format!("({}{})", operator, format_expr(right)),
+            ast::Expr::Infix(left, operator, right) => {
+                format!(
+                    "({} {} {})",
+                    format_expr(left),
+                    operator,
+                    format_expr(right)
+                )
+            }
+        }
+    }
 }
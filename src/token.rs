@@ -0,0 +1,34 @@
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Illegal,
+    Eof,
+    Ident(String),
+    Int(i64),
+    Float(String),
+    String(String),
+    Assign,
+    Plus,
+    Minus,
+    Asterisk,
+    Slash,
+    Comma,
+    Semicolon,
+    Bang,
+    Eq,
+    NotEq,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    Lparen,
+    Rparen,
+    Lbrace,
+    Rbrace,
+    Func,
+    Let,
+    If,
+    Else,
+    True,
+    False,
+    Return,
+}